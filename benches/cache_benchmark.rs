@@ -1,10 +1,11 @@
 use cache_lru::cache::{ Cache, LRUCache };
 use criterion::{ black_box, criterion_group, criterion_main, Criterion };
+use std::num::NonZeroUsize;
 
 fn cache_insertion_benchmark(c: &mut Criterion) {
     c.bench_function("insert 1000 items", |b| {
         b.iter(|| {
-            let mut cache = Cache::new(black_box(1000));
+            let mut cache = Cache::new(black_box(NonZeroUsize::new(1000).unwrap()));
             for i in 0..1000 {
                 cache.put(i, i);
             }
@@ -13,7 +14,7 @@ fn cache_insertion_benchmark(c: &mut Criterion) {
 }
 
 fn cache_get_benchmark(c: &mut Criterion) {
-    let mut cache = Cache::new(1000);
+    let mut cache = Cache::new(NonZeroUsize::new(1000).unwrap());
     for i in 0..1000 {
         cache.put(i, i);
     }
@@ -30,7 +31,7 @@ fn cache_get_benchmark(c: &mut Criterion) {
 fn cache_update_benchmark(c: &mut Criterion) {
     c.bench_function("update existing items", |b| {
         b.iter(|| {
-            let mut cache = Cache::new(black_box(100));
+            let mut cache = Cache::new(black_box(NonZeroUsize::new(100).unwrap()));
             for i in 0..100 {
                 cache.put(i, i);
                 cache.put(i, i + 1);
@@ -1,8 +1,9 @@
 use cache_lru::cache::{ Cache, LRUCache };
+use std::num::NonZeroUsize;
 use std::time::Instant;
 
 fn main() {
-    let mut cache = Cache::new(1000);
+    let mut cache = Cache::new(NonZeroUsize::new(1000).unwrap());
     let start = Instant::now();
 
     // Test d'insertion
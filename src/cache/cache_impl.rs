@@ -1,92 +1,406 @@
-use std::collections::{ HashMap, VecDeque };
-use std::hash::Hash;
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{ BuildHasher, Hash };
 use std::fmt::Display;
 use std::str::FromStr;
-use std::fs::{ File, OpenOptions };
-use std::io::{ self, BufRead, BufReader, Write };
+use std::fs::{ self, File, OpenOptions };
+use std::io::{ self, Read, Write };
+use std::num::NonZeroUsize;
 use super::LRUCache;
+use super::format::PersistenceFormat;
+use super::flush_policy::FlushPolicy;
 
 #[derive(Debug)]
-pub struct Cache<K: Eq + Hash + Clone + Display + FromStr, V: Display + FromStr> {
-    capacity: usize,
-    map: HashMap<K, V>,
-    order: VecDeque<K>,
+struct Node<K> {
+    prev: Option<K>,
+    next: Option<K>,
+}
+
+#[derive(Debug)]
+struct Entry<K, V> {
+    value: V,
+    weight: usize,
+    node: Node<K>,
+}
+
+#[derive(Debug)]
+pub struct Cache<K: Eq + Hash + Clone + Display + FromStr, V: Display + FromStr, S: BuildHasher = RandomState> {
+    capacity: NonZeroUsize,
+    map: HashMap<K, Entry<K, V>, S>,
+    head: Option<K>,
+    tail: Option<K>,
+    total_weight: usize,
     filename: Option<String>,
+    format: PersistenceFormat,
+    policy: FlushPolicy,
+    dirty: bool,
+    pending_writes: usize,
 }
 
-impl<K: Eq + Hash + Clone + Display + FromStr, V: Display + FromStr> Cache<K, V> {
-    pub fn new(capacity: usize) -> Self {
+impl<K: Eq + Hash + Clone + Display + FromStr, V: Display + FromStr> Cache<K, V, RandomState> {
+    pub fn new(capacity: NonZeroUsize) -> Self {
         Cache {
             capacity,
             map: HashMap::new(),
-            order: VecDeque::with_capacity(capacity),
+            head: None,
+            tail: None,
+            total_weight: 0,
             filename: None,
+            format: PersistenceFormat::default(),
+            policy: FlushPolicy::default(),
+            dirty: false,
+            pending_writes: 0,
         }
     }
 
-    pub fn new_persistent(capacity: usize, filename: &str) -> io::Result<Self> {
+    pub fn new_persistent(capacity: NonZeroUsize, filename: &str) -> io::Result<Self> {
+        Self::new_persistent_with_format_and_policy(
+            capacity,
+            filename,
+            PersistenceFormat::default(),
+            FlushPolicy::default()
+        )
+    }
+
+    /// Crée un cache persistant en choisissant explicitement le format d'encodage
+    /// utilisé pour le fichier de sauvegarde (voir [`PersistenceFormat`]).
+    pub fn new_persistent_with_format(
+        capacity: NonZeroUsize,
+        filename: &str,
+        format: PersistenceFormat
+    ) -> io::Result<Self> {
+        Self::new_persistent_with_format_and_policy(capacity, filename, format, FlushPolicy::default())
+    }
+
+    /// Crée un cache persistant en choisissant explicitement la politique de
+    /// réécriture du fichier de sauvegarde (voir [`FlushPolicy`]).
+    pub fn new_persistent_with_policy(
+        capacity: NonZeroUsize,
+        filename: &str,
+        policy: FlushPolicy
+    ) -> io::Result<Self> {
+        Self::new_persistent_with_format_and_policy(capacity, filename, PersistenceFormat::default(), policy)
+    }
+
+    /// Crée un cache persistant en choisissant explicitement le format d'encodage
+    /// et la politique de réécriture du fichier de sauvegarde.
+    ///
+    /// Le chargement initial n'applique pas `policy` : les entrées relues
+    /// depuis `filename` ne déclenchent aucune réécriture, pour éviter de
+    /// réécrire le fichier une fois par entrée lors d'un chargement en masse.
+    pub fn new_persistent_with_format_and_policy(
+        capacity: NonZeroUsize,
+        filename: &str,
+        format: PersistenceFormat,
+        policy: FlushPolicy
+    ) -> io::Result<Self> {
         let mut cache = Cache {
             capacity,
             map: HashMap::new(),
-            order: VecDeque::with_capacity(capacity),
-            filename: Some(filename.to_string()),
+            head: None,
+            tail: None,
+            total_weight: 0,
+            filename: None,
+            format,
+            policy,
+            dirty: false,
+            pending_writes: 0,
         };
 
-        if let Ok(file) = File::open(filename) {
-            let reader = BufReader::new(file);
-            for line in reader.lines() {
-                let line = line?;
-                let parts: Vec<&str> = line.split('\t').collect();
-                if parts.len() == 2 {
-                    if let (Ok(key), Ok(value)) = (K::from_str(parts[0]), V::from_str(parts[1])) {
-                        cache.put(key, value);
-                    }
-                }
+        if let Ok(mut file) = File::open(filename) {
+            let mut bytes = Vec::new();
+            file.read_to_end(&mut bytes)?;
+            for (key, value) in format.decode(&bytes)? {
+                cache.put(key, value);
             }
         }
 
+        cache.filename = Some(filename.to_string());
+        cache.dirty = false;
+        cache.pending_writes = 0;
+
         Ok(cache)
     }
+}
+
+impl<K: Eq + Hash + Clone + Display + FromStr, V: Display + FromStr, S: BuildHasher> Cache<K, V, S> {
+    /// Crée un cache vide utilisant `hasher` au lieu du hasher par défaut
+    /// (résistant aux attaques par collision mais coûteux). Permet de brancher
+    /// un hasher plus rapide et non cryptographique (FxHash, ahash, ...) pour
+    /// des clés de confiance, par exemple des entiers.
+    pub fn with_hasher(capacity: NonZeroUsize, hasher: S) -> Self {
+        Cache {
+            capacity,
+            map: HashMap::with_hasher(hasher),
+            head: None,
+            tail: None,
+            total_weight: 0,
+            filename: None,
+            format: PersistenceFormat::default(),
+            policy: FlushPolicy::default(),
+            dirty: false,
+            pending_writes: 0,
+        }
+    }
+
+    /// Réécrit immédiatement le fichier de sauvegarde si le cache est persistant,
+    /// quelle que soit la [`FlushPolicy`] configurée, puis efface le drapeau "dirty".
+    pub fn flush(&mut self) -> io::Result<()> {
+        if let Some(filename) = self.filename.clone() {
+            self.save_to_file(&filename)?;
+        }
+        self.dirty = false;
+        self.pending_writes = 0;
+        Ok(())
+    }
+
+    /// Marque le cache comme modifié et applique la [`FlushPolicy`] courante :
+    /// réécriture immédiate, comptage jusqu'au seuil `EveryN`, ou rien en `Manual`.
+    fn record_mutation(&mut self) {
+        self.dirty = true;
+
+        if self.filename.is_none() {
+            return;
+        }
+
+        match self.policy {
+            FlushPolicy::EveryWrite => {
+                let _ = self.flush();
+            }
+            FlushPolicy::EveryN(n) => {
+                self.pending_writes += 1;
+                if self.pending_writes >= n.get() {
+                    let _ = self.flush();
+                }
+            }
+            FlushPolicy::Manual => {}
+        }
+    }
 
     pub fn save_to_file(&self, filename: &str) -> io::Result<()> {
-        let mut file = OpenOptions::new().write(true).create(true).truncate(true).open(filename)?;
+        let mut entries: Vec<(&K, &V)> = Vec::new();
+        let mut current = self.tail.clone();
+        while let Some(key) = current {
+            if let Some((stored_key, entry)) = self.map.get_key_value(&key) {
+                entries.push((stored_key, &entry.value));
+                current = entry.node.prev.clone();
+            } else {
+                break;
+            }
+        }
 
-        for key in &self.order {
-            if let Some(value) = self.map.get(key) {
-                writeln!(file, "{}\t{}", key, value)?;
+        let bytes = self.format.encode(&entries)?;
+        write_atomic(filename, &bytes)
+    }
+
+    fn remove_node(&mut self, key: &K) {
+        let (prev, next) = match self.map.get(key) {
+            Some(entry) => (entry.node.prev.clone(), entry.node.next.clone()),
+            None => return,
+        };
+
+        match (prev.clone(), next.clone()) {
+            (Some(prev_key), Some(next_key)) => {
+                if let Some(entry) = self.map.get_mut(&prev_key) {
+                    entry.node.next = Some(next_key.clone());
+                }
+                if let Some(entry) = self.map.get_mut(&next_key) {
+                    entry.node.prev = Some(prev_key);
+                }
+            }
+            (None, Some(next_key)) => {
+                self.head = Some(next_key.clone());
+                if let Some(entry) = self.map.get_mut(&next_key) {
+                    entry.node.prev = None;
+                }
+            }
+            (Some(prev_key), None) => {
+                self.tail = Some(prev_key.clone());
+                if let Some(entry) = self.map.get_mut(&prev_key) {
+                    entry.node.next = None;
+                }
+            }
+            (None, None) => {
+                self.head = None;
+                self.tail = None;
             }
         }
+    }
 
-        Ok(())
+    fn add_to_head(&mut self, key: K) {
+        if let Some(old_head) = self.head.clone() {
+            if let Some(entry) = self.map.get_mut(&old_head) {
+                entry.node.prev = Some(key.clone());
+            }
+        } else {
+            self.tail = Some(key.clone());
+        }
+
+        if let Some(entry) = self.map.get_mut(&key) {
+            entry.node.next = self.head.clone();
+            entry.node.prev = None;
+        }
+
+        self.head = Some(key);
     }
-}
 
-impl<K: Eq + Hash + Clone + Display + FromStr, V: Display + FromStr> LRUCache<K, V>
-for Cache<K, V> {
-    fn put(&mut self, key: K, value: V) {
+    fn move_to_head(&mut self, key: &K) {
+        if self.head.as_ref() != Some(key) {
+            let key_clone = key.clone();
+            self.remove_node(key);
+            self.add_to_head(key_clone);
+        }
+    }
+
+    fn evict_tail(&mut self) -> Option<(K, V)> {
+        let tail_key = self.tail.clone()?;
+        self.remove_node(&tail_key);
+        let entry = self.map.remove(&tail_key)?;
+        self.total_weight -= entry.weight;
+        Some((tail_key, entry.value))
+    }
+
+    /// Insère une paire clé-valeur avec un poids explicite.
+    ///
+    /// Le cache borne la somme des poids (`total_weight`) à `capacity` au lieu
+    /// du nombre d'éléments : des entrées les moins récemment utilisées sont
+    /// évincées en boucle jusqu'à ce que la nouvelle entrée tienne.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(())` - L'insertion a réussi
+    /// * `None` - Le poids de l'élément dépasse à lui seul la capacité
+    pub fn put_with_weight(&mut self, key: K, value: V, weight: usize) -> Option<()> {
+        if weight > self.capacity.get() {
+            return None;
+        }
+
         if self.map.contains_key(&key) {
-            self.order.retain(|k| k != &key);
-        } else if self.map.len() == self.capacity {
-            if let Some(old_key) = self.order.pop_front() {
-                self.map.remove(&old_key);
+            self.remove_node(&key);
+            let old_entry = self.map.remove(&key).unwrap();
+            self.total_weight -= old_entry.weight;
+        }
+
+        while self.total_weight + weight > self.capacity.get() {
+            if self.evict_tail().is_none() {
+                break;
             }
         }
 
-        self.map.insert(key.clone(), value);
-        self.order.push_back(key);
+        self.total_weight += weight;
+        self.map.insert(key.clone(), Entry {
+            value,
+            weight,
+            node: Node { prev: None, next: None },
+        });
+        self.add_to_head(key);
+        self.record_mutation();
+
+        Some(())
+    }
+
+    /// Retourne la capacité courante du cache (en unités de poids).
+    pub fn capacity(&self) -> usize {
+        self.capacity.get()
+    }
+
+    /// Modifie la capacité du cache.
+    ///
+    /// Si la nouvelle capacité est inférieure au poids total actuellement
+    /// stocké, les entrées les moins récemment utilisées sont évincées en
+    /// boucle jusqu'à ce que `total_weight <= capacity`. Comme `capacity`
+    /// borne la somme des poids et non le nombre d'entrées, `len() <= capacity`
+    /// n'est garanti qu'en l'absence de `put_with_weight` (poids tous égaux à 1).
+    pub fn set_capacity(&mut self, capacity: NonZeroUsize) {
+        self.capacity = capacity;
 
-        if let Some(filename) = &self.filename {
-            let _ = self.save_to_file(filename);
+        while self.total_weight > self.capacity.get() {
+            if self.evict_tail().is_none() {
+                break;
+            }
         }
     }
+}
+
+impl<K: Eq + Hash + Clone + Display + FromStr, V: Display + FromStr, S: BuildHasher> LRUCache<K, V>
+for Cache<K, V, S> {
+    fn put(&mut self, key: K, value: V) {
+        self.put_with_weight(key, value, 1);
+    }
 
     fn get(&mut self, key: &K) -> Option<&V> {
         if self.map.contains_key(key) {
-            self.order.retain(|k| k != key);
-            self.order.push_back(key.clone());
-            self.map.get(key)
+            self.move_to_head(key);
+            self.map.get(key).map(|entry| &entry.value)
         } else {
             None
         }
     }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.map.get(key).map(|entry| &entry.value)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        if self.map.contains_key(key) {
+            self.move_to_head(key);
+            // Le caller reçoit une &mut V : on ne sait pas s'il va réellement
+            // muter la valeur, donc on marque le cache "dirty" par prudence
+            // pour que la `FlushPolicy` persiste ce get_mut comme une écriture.
+            self.record_mutation();
+            // `record_mutation` a pu réécrire le fichier avant que le caller ne
+            // modifie la valeur : le cache reste "dirty" pour que la modification
+            // soit écrite au prochain flush ou au `Drop`.
+            self.dirty = true;
+            self.map.get_mut(key).map(|entry| &mut entry.value)
+        } else {
+            None
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove_node(key);
+        let entry = self.map.remove(key)?;
+        self.total_weight -= entry.weight;
+        self.record_mutation();
+        Some(entry.value)
+    }
+}
+
+impl<K: Eq + Hash + Clone + Display + FromStr, V: Display + FromStr, S: BuildHasher> Drop for Cache<K, V, S> {
+    fn drop(&mut self) {
+        if self.dirty {
+            let _ = self.flush();
+        }
+    }
+}
+
+/// Écrit `bytes` dans `filename` de façon atomique : le contenu est d'abord
+/// écrit dans un fichier temporaire puis déplacé en place par un `rename`,
+/// pour qu'un crash en cours d'écriture ne puisse pas tronquer un instantané
+/// existant.
+fn write_atomic(filename: &str, bytes: &[u8]) -> io::Result<()> {
+    let tmp_filename = format!("{}.tmp", filename);
+    {
+        let mut tmp_file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp_filename)?;
+        tmp_file.write_all(bytes)?;
+        tmp_file.sync_all()?;
+    }
+    fs::rename(&tmp_filename, filename)
 }
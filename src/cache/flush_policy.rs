@@ -0,0 +1,20 @@
+use std::num::NonZeroUsize;
+
+/// Politique de persistance appliquée par [`super::Cache`] après une mutation.
+///
+/// Réécrire tout le fichier à chaque `put` coûte O(n) par opération ; cette
+/// politique permet de différer l'écriture pour les chargements en masse,
+/// au prix d'une perte de durabilité en cas de crash avant le prochain flush.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlushPolicy {
+    /// Réécrit le fichier après chaque mutation (comportement historique,
+    /// le plus sûr mais le plus coûteux).
+    #[default]
+    EveryWrite,
+    /// Réécrit le fichier toutes les `N` mutations, ou lors d'un `flush`/`Drop`
+    /// explicite si `N` mutations n'ont pas encore été atteintes.
+    EveryN(NonZeroUsize),
+    /// Ne réécrit jamais automatiquement : seul un appel explicite à
+    /// `flush` (ou le `Drop` du cache) persiste les changements.
+    Manual,
+}
@@ -0,0 +1,133 @@
+use std::fmt::Display;
+use std::io;
+use std::str::FromStr;
+
+/// Format de sérialisation utilisé pour persister un cache sur disque.
+///
+/// Sélectionné à la construction d'un cache persistant ; déterminé
+/// uniquement ici plutôt que dans `save_to_file`/`new_persistent` pour que
+/// l'encodage reste cohérent entre la sauvegarde et le rechargement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PersistenceFormat {
+    /// Format historique `clé\tvaleur\n`. Corrompu si une clé ou une valeur
+    /// contient une tabulation ou un retour à la ligne ; conservé pour la
+    /// compatibilité ascendante.
+    #[default]
+    Tsv,
+    /// Encodage binaire à longueurs préfixées : sûr quel que soit le texte
+    /// produit par `Display`/`FromStr` pour `K` et `V`.
+    Binary,
+    /// Encodage JSON, nécessite la feature `json-format`.
+    #[cfg(feature = "json-format")]
+    Json,
+}
+
+impl PersistenceFormat {
+    pub(crate) fn encode<K: Display, V: Display>(self, entries: &[(&K, &V)]) -> io::Result<Vec<u8>> {
+        match self {
+            PersistenceFormat::Tsv => {
+                let mut out = String::new();
+                for (key, value) in entries {
+                    out.push_str(&key.to_string());
+                    out.push('\t');
+                    out.push_str(&value.to_string());
+                    out.push('\n');
+                }
+                Ok(out.into_bytes())
+            }
+            PersistenceFormat::Binary => {
+                let mut out = Vec::new();
+                for (key, value) in entries {
+                    write_length_prefixed(&mut out, &key.to_string());
+                    write_length_prefixed(&mut out, &value.to_string());
+                }
+                Ok(out)
+            }
+            #[cfg(feature = "json-format")]
+            PersistenceFormat::Json => {
+                let records: Vec<JsonRecord> = entries
+                    .iter()
+                    .map(|(key, value)| JsonRecord {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                    })
+                    .collect();
+                serde_json::to_vec(&records).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+        }
+    }
+
+    pub(crate) fn decode<K: FromStr, V: FromStr>(self, bytes: &[u8]) -> io::Result<Vec<(K, V)>> {
+        match self {
+            PersistenceFormat::Tsv => {
+                let text = String::from_utf8_lossy(bytes);
+                let mut entries = Vec::new();
+                for line in text.lines() {
+                    let parts: Vec<&str> = line.split('\t').collect();
+                    if parts.len() == 2 {
+                        if let (Ok(key), Ok(value)) = (K::from_str(parts[0]), V::from_str(parts[1])) {
+                            entries.push((key, value));
+                        }
+                    }
+                }
+                Ok(entries)
+            }
+            PersistenceFormat::Binary => {
+                let mut entries = Vec::new();
+                let mut cursor = bytes;
+                while !cursor.is_empty() {
+                    let key_str = read_length_prefixed(&mut cursor)?;
+                    let value_str = read_length_prefixed(&mut cursor)?;
+                    if let (Ok(key), Ok(value)) = (K::from_str(&key_str), V::from_str(&value_str)) {
+                        entries.push((key, value));
+                    }
+                }
+                Ok(entries)
+            }
+            #[cfg(feature = "json-format")]
+            PersistenceFormat::Json => {
+                let records: Vec<JsonRecord> = serde_json
+                    ::from_slice(bytes)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                let mut entries = Vec::new();
+                for record in records {
+                    if
+                        let (Ok(key), Ok(value)) = (
+                            K::from_str(&record.key),
+                            V::from_str(&record.value),
+                        )
+                    {
+                        entries.push((key, value));
+                    }
+                }
+                Ok(entries)
+            }
+        }
+    }
+}
+
+fn write_length_prefixed(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+fn read_length_prefixed(cursor: &mut &[u8]) -> io::Result<String> {
+    if cursor.len() < 4 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "préfixe de longueur tronqué"));
+    }
+    let (len_bytes, rest) = cursor.split_at(4);
+    let len = u32::from_le_bytes(len_bytes.try_into().unwrap()) as usize;
+    if rest.len() < len {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "enregistrement tronqué"));
+    }
+    let (str_bytes, rest) = rest.split_at(len);
+    *cursor = rest;
+    String::from_utf8(str_bytes.to_vec()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(feature = "json-format")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct JsonRecord {
+    key: String,
+    value: String,
+}
@@ -1,8 +1,14 @@
 mod traits;
 mod persistence;
+mod format;
+mod flush_policy;
 
 pub use traits::LRUCache;
 pub use persistence::Persistence;
+pub use format::PersistenceFormat;
+pub use flush_policy::FlushPolicy;
 pub use crate::cache::cache_impl::Cache;
+pub use crate::cache::two_queue::TwoQueueCache;
 
 mod cache_impl;
+mod two_queue;
@@ -29,4 +29,57 @@ pub trait LRUCache<K, V> {
     /// * `Some(&V)` - Une référence à la valeur si la clé existe
     /// * `None` - Si la clé n'existe pas
     fn get(&mut self, key: &K) -> Option<&V>;
+
+    /// Retourne le nombre d'entrées actuellement stockées dans le cache
+    fn len(&self) -> usize;
+
+    /// Indique si le cache ne contient aucune entrée
+    fn is_empty(&self) -> bool;
+
+    /// Indique si la clé est présente dans le cache, sans modifier l'ordre LRU
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - La clé à rechercher
+    fn contains_key(&self, key: &K) -> bool;
+
+    /// Récupère une référence à la valeur associée à la clé sans la promouvoir
+    ///
+    /// Contrairement à `get`, l'ordre LRU n'est pas modifié.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - La clé à rechercher
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&V)` - Une référence à la valeur si la clé existe
+    /// * `None` - Si la clé n'existe pas
+    fn peek(&self, key: &K) -> Option<&V>;
+
+    /// Récupère une référence mutable à la valeur associée à la clé
+    ///
+    /// Met à jour l'ordre LRU en déplaçant l'élément en tête, comme `get`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - La clé à rechercher
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&mut V)` - Une référence mutable à la valeur si la clé existe
+    /// * `None` - Si la clé n'existe pas
+    fn get_mut(&mut self, key: &K) -> Option<&mut V>;
+
+    /// Supprime une clé du cache et retourne sa valeur
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - La clé à supprimer
+    ///
+    /// # Returns
+    ///
+    /// * `Some(V)` - La valeur possédée si la clé existait
+    /// * `None` - Si la clé n'existe pas
+    fn remove(&mut self, key: &K) -> Option<V>;
 }
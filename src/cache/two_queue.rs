@@ -0,0 +1,154 @@
+use std::collections::hash_map::Entry;
+use std::collections::{ HashMap, VecDeque };
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use super::LRUCache;
+
+/// Cache implémentant l'algorithme 2Q, résistant aux scans.
+///
+/// Contrairement à une LRU classique, un parcours ponctuel d'un grand nombre
+/// de clés (un "scan") ne chasse pas les entrées réellement populaires : les
+/// nouvelles clés transitent d'abord par `A1in` (et sa liste fantôme
+/// `A1out`) avant de pouvoir rejoindre la LRU `Am` des entrées "chaudes".
+///
+/// * `Am` - liste LRU des entrées promues (accédées au moins deux fois)
+/// * `A1in` - FIFO des entrées vues pour la première fois, avec leur valeur
+/// * `A1out` - FIFO fantôme des clés évincées de `A1in` (valeurs abandonnées)
+#[derive(Debug)]
+pub struct TwoQueueCache<K: Eq + Hash + Clone, V> {
+    capacity: NonZeroUsize,
+    k_in: usize,
+    k_out: usize,
+    am: VecDeque<K>,
+    am_map: HashMap<K, V>,
+    a1in: VecDeque<K>,
+    a1in_map: HashMap<K, V>,
+    a1out: VecDeque<K>,
+}
+
+impl<K: Eq + Hash + Clone, V> TwoQueueCache<K, V> {
+    /// Crée un cache 2Q avec `Kin` = 25% et `Kout` = 50% de la capacité.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        let cap = capacity.get();
+        TwoQueueCache {
+            capacity,
+            k_in: (cap / 4).max(1),
+            k_out: (cap / 2).max(1),
+            am: VecDeque::new(),
+            am_map: HashMap::new(),
+            a1in: VecDeque::new(),
+            a1in_map: HashMap::new(),
+            a1out: VecDeque::new(),
+        }
+    }
+
+    fn move_am_to_mru(&mut self, key: &K) {
+        if let Some(pos) = self.am.iter().position(|k| k == key) {
+            self.am.remove(pos);
+        }
+        self.am.push_back(key.clone());
+    }
+
+    fn evict(&mut self) {
+        while self.a1in.len() > self.k_in {
+            if let Some(old_key) = self.a1in.pop_back() {
+                self.a1in_map.remove(&old_key);
+                self.a1out.push_front(old_key);
+
+                while self.a1out.len() > self.k_out {
+                    self.a1out.pop_back();
+                }
+            }
+        }
+
+        while self.am.len() + self.a1in.len() > self.capacity.get() {
+            match self.am.pop_front() {
+                Some(old_key) => {
+                    self.am_map.remove(&old_key);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> LRUCache<K, V> for TwoQueueCache<K, V> {
+    fn put(&mut self, key: K, value: V) {
+        if self.am_map.contains_key(&key) {
+            self.am_map.insert(key.clone(), value);
+            self.move_am_to_mru(&key);
+            return;
+        }
+
+        let key = match self.a1in_map.entry(key) {
+            Entry::Occupied(mut entry) => {
+                entry.insert(value);
+                return;
+            }
+            Entry::Vacant(entry) => entry.into_key(),
+        };
+
+        if let Some(pos) = self.a1out.iter().position(|k| k == &key) {
+            self.a1out.remove(pos);
+            self.am_map.insert(key.clone(), value);
+            self.am.push_back(key);
+        } else {
+            self.a1in.push_front(key.clone());
+            self.a1in_map.insert(key, value);
+        }
+
+        self.evict();
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        if self.am_map.contains_key(key) {
+            self.move_am_to_mru(key);
+            self.am_map.get(key)
+        } else {
+            self.a1in_map.get(key)
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.am_map.len() + self.a1in_map.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.am_map.contains_key(key) || self.a1in_map.contains_key(key)
+    }
+
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.am_map.get(key).or_else(|| self.a1in_map.get(key))
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        if self.am_map.contains_key(key) {
+            self.move_am_to_mru(key);
+            self.am_map.get_mut(key)
+        } else {
+            self.a1in_map.get_mut(key)
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        if let Some(value) = self.am_map.remove(key) {
+            if let Some(pos) = self.am.iter().position(|k| k == key) {
+                self.am.remove(pos);
+            }
+            return Some(value);
+        }
+
+        if let Some(value) = self.a1in_map.remove(key) {
+            if let Some(pos) = self.a1in.iter().position(|k| k == key) {
+                self.a1in.remove(pos);
+            }
+            return Some(value);
+        }
+
+        None
+    }
+}
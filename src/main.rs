@@ -1,8 +1,12 @@
 use cache_lru::cache::{ Cache, LRUCache };
+use std::num::NonZeroUsize;
 
 fn main() {
     // Créer un cache persistant avec une capacité de 3
-    let mut cache = Cache::<String, i32>::new_persistent(3, "mon_cache.txt").unwrap();
+    let mut cache = Cache::<String, i32>::new_persistent(
+        NonZeroUsize::new(3).unwrap(),
+        "mon_cache.txt"
+    ).unwrap();
 
     // Test du cache
     cache.put("A".to_string(), 1);
@@ -1,8 +1,28 @@
-use cache_lru::cache::{ Cache, LRUCache };
+use cache_lru::cache::{ Cache, FlushPolicy, LRUCache, PersistenceFormat, TwoQueueCache };
+use std::hash::{ BuildHasherDefault, Hasher };
+use std::num::NonZeroUsize;
+
+/// Hasher déterministe (non-cryptographique) utilisé pour vérifier que
+/// `Cache::with_hasher` branche réellement le `BuildHasher` fourni au lieu
+/// de retomber sur `RandomState`.
+#[derive(Default)]
+struct ConstantHasher(u64);
+
+impl Hasher for ConstantHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = self.0.wrapping_mul(31).wrapping_add(byte as u64);
+        }
+    }
+}
 
 #[test]
 fn test_basic_operations() {
-    let mut cache = Cache::<String, i32>::new(2);
+    let mut cache = Cache::<String, i32>::new(NonZeroUsize::new(2).unwrap());
     cache.put("a".to_string(), 1);
     cache.put("b".to_string(), 2);
     assert_eq!(cache.get(&"a".to_string()), Some(&1));
@@ -14,7 +34,7 @@ fn test_basic_operations() {
 
 #[test]
 fn test_capacity() {
-    let mut cache = Cache::<String, i32>::new(2);
+    let mut cache = Cache::<String, i32>::new(NonZeroUsize::new(2).unwrap());
     cache.put("A".to_string(), 1);
     cache.put("B".to_string(), 2);
     cache.put("C".to_string(), 3);
@@ -25,7 +45,7 @@ fn test_capacity() {
 
 #[test]
 fn test_update_existing() {
-    let mut cache = Cache::<String, i32>::new(2);
+    let mut cache = Cache::<String, i32>::new(NonZeroUsize::new(2).unwrap());
     cache.put("A".to_string(), 1);
     cache.put("B".to_string(), 2);
     cache.put("A".to_string(), 3);
@@ -33,21 +53,309 @@ fn test_update_existing() {
     assert_eq!(cache.get(&"B".to_string()), Some(&2));
 }
 
+#[test]
+fn test_eviction_order_after_update() {
+    let mut cache = Cache::<String, i32>::new(NonZeroUsize::new(2).unwrap());
+    cache.put("A".to_string(), 1);
+    cache.put("B".to_string(), 2);
+    cache.put("A".to_string(), 3);
+    cache.put("C".to_string(), 4);
+    assert_eq!(cache.get(&"A".to_string()), Some(&3));
+    assert_eq!(cache.get(&"B".to_string()), None);
+    assert_eq!(cache.get(&"C".to_string()), Some(&4));
+}
+
+#[test]
+fn test_remove() {
+    let mut cache = Cache::<String, i32>::new(NonZeroUsize::new(2).unwrap());
+    cache.put("A".to_string(), 1);
+    cache.put("B".to_string(), 2);
+    assert_eq!(cache.remove(&"A".to_string()), Some(1));
+    assert_eq!(cache.get(&"A".to_string()), None);
+    assert_eq!(cache.len(), 1);
+    cache.put("C".to_string(), 3);
+    cache.put("D".to_string(), 4);
+    assert_eq!(cache.get(&"B".to_string()), None);
+    assert_eq!(cache.get(&"C".to_string()), Some(&3));
+    assert_eq!(cache.get(&"D".to_string()), Some(&4));
+}
+
+#[test]
+fn test_with_hasher_uses_provided_build_hasher() {
+    let mut cache: Cache<String, i32, BuildHasherDefault<ConstantHasher>> = Cache::with_hasher(
+        NonZeroUsize::new(2).unwrap(),
+        BuildHasherDefault::default()
+    );
+    cache.put("A".to_string(), 1);
+    cache.put("B".to_string(), 2);
+    assert_eq!(cache.get(&"A".to_string()), Some(&1));
+    cache.put("C".to_string(), 3);
+    assert_eq!(cache.get(&"B".to_string()), None);
+    assert_eq!(cache.get(&"A".to_string()), Some(&1));
+    assert_eq!(cache.get(&"C".to_string()), Some(&3));
+}
+
+#[test]
+fn test_get_mut() {
+    let mut cache = Cache::<String, i32>::new(NonZeroUsize::new(2).unwrap());
+    cache.put("A".to_string(), 1);
+    cache.put("B".to_string(), 2);
+    if let Some(value) = cache.get_mut(&"A".to_string()) {
+        *value = 10;
+    }
+    assert_eq!(cache.get(&"A".to_string()), Some(&10));
+    cache.put("C".to_string(), 3);
+    assert_eq!(cache.get(&"B".to_string()), None);
+    assert_eq!(cache.get(&"A".to_string()), Some(&10));
+}
+
+#[test]
+fn test_get_mut_marks_dirty_and_drop_flushes() {
+    let filename = "test_cache_get_mut.txt";
+    {
+        let mut cache = Cache::<String, i32>::new_persistent_with_policy(
+            NonZeroUsize::new(2).unwrap(),
+            filename,
+            FlushPolicy::Manual
+        ).unwrap();
+        cache.put("A".to_string(), 1);
+        cache.flush().unwrap();
+        if let Some(value) = cache.get_mut(&"A".to_string()) {
+            *value = 42;
+        }
+    }
+
+    {
+        let cache = Cache::<String, i32>::new_persistent(NonZeroUsize::new(2).unwrap(), filename).unwrap();
+        assert_eq!(cache.peek(&"A".to_string()), Some(&42));
+    }
+
+    std::fs::remove_file(filename).unwrap();
+}
+
+#[test]
+fn test_persistence_round_trip_after_update() {
+    let filename = "test_cache_update.txt";
+    {
+        let mut cache = Cache::<String, i32>::new_persistent(NonZeroUsize::new(2).unwrap(), filename).unwrap();
+        cache.put("A".to_string(), 1);
+        cache.put("B".to_string(), 2);
+        cache.put("A".to_string(), 3);
+        cache.put("C".to_string(), 4);
+        cache.save_to_file(filename).unwrap();
+    }
+
+    {
+        let mut cache = Cache::<String, i32>::new_persistent(NonZeroUsize::new(2).unwrap(), filename).unwrap();
+        assert_eq!(cache.get(&"A".to_string()), Some(&3));
+        assert_eq!(cache.get(&"B".to_string()), None);
+        assert_eq!(cache.get(&"C".to_string()), Some(&4));
+    }
+
+    std::fs::remove_file(filename).unwrap();
+}
+
+#[test]
+fn test_put_with_weight_rejects_oversized_entry() {
+    let mut cache = Cache::<String, i32>::new(NonZeroUsize::new(4).unwrap());
+    assert_eq!(cache.put_with_weight("A".to_string(), 1, 5), None);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_put_with_weight_evicts_until_it_fits() {
+    let mut cache = Cache::<String, i32>::new(NonZeroUsize::new(4).unwrap());
+    cache.put_with_weight("A".to_string(), 1, 1);
+    cache.put_with_weight("B".to_string(), 2, 1);
+    cache.put_with_weight("C".to_string(), 3, 1);
+    assert_eq!(cache.put_with_weight("D".to_string(), 4, 3), Some(()));
+    assert_eq!(cache.get(&"A".to_string()), None);
+    assert_eq!(cache.get(&"B".to_string()), None);
+    assert_eq!(cache.get(&"C".to_string()), Some(&3));
+    assert_eq!(cache.get(&"D".to_string()), Some(&4));
+}
+
+#[test]
+fn test_set_capacity_shrinks_and_evicts_lru() {
+    let mut cache = Cache::<String, i32>::new(NonZeroUsize::new(4).unwrap());
+    cache.put("A".to_string(), 1);
+    cache.put("B".to_string(), 2);
+    cache.put("C".to_string(), 3);
+    cache.put("D".to_string(), 4);
+    cache.set_capacity(NonZeroUsize::new(2).unwrap());
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(&"A".to_string()), None);
+    assert_eq!(cache.get(&"B".to_string()), None);
+    assert_eq!(cache.get(&"C".to_string()), Some(&3));
+    assert_eq!(cache.get(&"D".to_string()), Some(&4));
+    assert_eq!(cache.capacity(), 2);
+}
+
 #[test]
 fn test_persistence() {
     let filename = "test_cache.txt";
     {
-        let mut cache = Cache::<String, i32>::new_persistent(2, filename).unwrap();
+        let mut cache = Cache::<String, i32>::new_persistent(NonZeroUsize::new(2).unwrap(), filename).unwrap();
         cache.put("test".to_string(), 123);
         cache.put("test2".to_string(), 456);
         cache.save_to_file(filename).unwrap();
     }
 
     {
-        let mut cache = Cache::<String, i32>::new_persistent(2, filename).unwrap();
+        let mut cache = Cache::<String, i32>::new_persistent(NonZeroUsize::new(2).unwrap(), filename).unwrap();
         assert_eq!(cache.get(&"test".to_string()), Some(&123));
         assert_eq!(cache.get(&"test2".to_string()), Some(&456));
     }
 
     std::fs::remove_file(filename).unwrap();
 }
+
+#[test]
+fn test_flush_policy_manual_writes_nothing_until_flush() {
+    let filename = "test_cache_manual_policy.txt";
+    let _ = std::fs::remove_file(filename);
+    {
+        let mut cache = Cache::<String, i32>::new_persistent_with_policy(
+            NonZeroUsize::new(2).unwrap(),
+            filename,
+            FlushPolicy::Manual
+        ).unwrap();
+        cache.put("A".to_string(), 1);
+        cache.put("B".to_string(), 2);
+        assert!(!std::path::Path::new(filename).exists());
+        cache.flush().unwrap();
+        assert!(std::fs::metadata(filename).unwrap().len() > 0);
+    }
+
+    std::fs::remove_file(filename).unwrap();
+}
+
+#[test]
+fn test_flush_policy_manual_flushes_pending_writes_on_drop() {
+    let filename = "test_cache_manual_drop.txt";
+    let _ = std::fs::remove_file(filename);
+    {
+        let mut cache = Cache::<String, i32>::new_persistent_with_policy(
+            NonZeroUsize::new(2).unwrap(),
+            filename,
+            FlushPolicy::Manual
+        ).unwrap();
+        cache.put("A".to_string(), 1);
+    }
+
+    {
+        let cache = Cache::<String, i32>::new_persistent(NonZeroUsize::new(2).unwrap(), filename).unwrap();
+        assert_eq!(cache.peek(&"A".to_string()), Some(&1));
+    }
+
+    std::fs::remove_file(filename).unwrap();
+}
+
+#[test]
+fn test_flush_policy_every_n_flushes_at_threshold() {
+    let filename = "test_cache_every_n_policy.txt";
+    let _ = std::fs::remove_file(filename);
+    {
+        let mut cache = Cache::<String, i32>::new_persistent_with_policy(
+            NonZeroUsize::new(3).unwrap(),
+            filename,
+            FlushPolicy::EveryN(NonZeroUsize::new(2).unwrap())
+        ).unwrap();
+        cache.put("A".to_string(), 1);
+        assert!(!std::path::Path::new(filename).exists());
+        cache.put("B".to_string(), 2);
+        assert!(std::fs::metadata(filename).unwrap().len() > 0);
+    }
+
+    std::fs::remove_file(filename).unwrap();
+}
+
+#[test]
+fn test_binary_format_round_trips_embedded_delimiters() {
+    let filename = "test_cache_binary_delims.txt";
+    {
+        let mut cache = Cache::<String, String>::new_persistent_with_format(
+            NonZeroUsize::new(2).unwrap(),
+            filename,
+            PersistenceFormat::Binary
+        ).unwrap();
+        cache.put("key\twith\ttabs".to_string(), "value\nwith\nnewlines\tand\ttabs".to_string());
+        cache.save_to_file(filename).unwrap();
+    }
+
+    {
+        let cache = Cache::<String, String>::new_persistent_with_format(
+            NonZeroUsize::new(2).unwrap(),
+            filename,
+            PersistenceFormat::Binary
+        ).unwrap();
+        assert_eq!(
+            cache.peek(&"key\twith\ttabs".to_string()),
+            Some(&"value\nwith\nnewlines\tand\ttabs".to_string())
+        );
+    }
+
+    std::fs::remove_file(filename).unwrap();
+}
+
+#[cfg(feature = "json-format")]
+#[test]
+fn test_json_format_round_trips_special_characters_and_preserves_order() {
+    let filename = "test_cache_json_format.txt";
+    let tricky_key = "key\"with\\backslash\tand\nnewline".to_string();
+    {
+        let mut cache = Cache::<String, String>::new_persistent_with_format(
+            NonZeroUsize::new(2).unwrap(),
+            filename,
+            PersistenceFormat::Json
+        ).unwrap();
+        cache.put(tricky_key.clone(), "value{\"embedded\":true}".to_string());
+        cache.put("B".to_string(), "2".to_string());
+        cache.put(tricky_key.clone(), "updated".to_string());
+        cache.put("C".to_string(), "3".to_string());
+        cache.save_to_file(filename).unwrap();
+    }
+
+    {
+        let mut cache = Cache::<String, String>::new_persistent_with_format(
+            NonZeroUsize::new(2).unwrap(),
+            filename,
+            PersistenceFormat::Json
+        ).unwrap();
+        assert_eq!(cache.get(&"B".to_string()), None);
+        assert_eq!(cache.get(&tricky_key), Some(&"updated".to_string()));
+        assert_eq!(cache.get(&"C".to_string()), Some(&"3".to_string()));
+
+        cache.put("D".to_string(), "4".to_string());
+        assert_eq!(cache.get(&tricky_key), None);
+        assert_eq!(cache.get(&"C".to_string()), Some(&"3".to_string()));
+        assert_eq!(cache.get(&"D".to_string()), Some(&"4".to_string()));
+    }
+
+    std::fs::remove_file(filename).unwrap();
+}
+
+#[test]
+fn test_two_queue_ghost_hit_promotes_to_am() {
+    let mut cache = TwoQueueCache::<i32, i32>::new(NonZeroUsize::new(4).unwrap());
+    cache.put(1, 100);
+    cache.put(2, 200);
+    assert!(!cache.contains_key(&1));
+    cache.put(1, 101);
+    assert_eq!(cache.get(&1), Some(&101));
+}
+
+#[test]
+fn test_two_queue_scan_resistance() {
+    let mut cache = TwoQueueCache::<i32, i32>::new(NonZeroUsize::new(4).unwrap());
+    cache.put(1, 100);
+    cache.put(2, 200);
+    cache.put(1, 100);
+    assert_eq!(cache.get(&1), Some(&100));
+
+    for i in 3..103 {
+        cache.put(i, i);
+    }
+
+    assert_eq!(cache.get(&1), Some(&100));
+}